@@ -11,17 +11,15 @@ pub struct Cli<'a, 'b> {
 }
 
 impl<'a, 'b> Cli<'a, 'b> {
-    fn initialize_parser() -> clap::App<'a, 'b> {
-        // Command line app
-        clap::App::new("tarsplit")
-            .version(env!("CARGO_PKG_VERSION"))
-            .author("All The Music, LLC")
-            .about("Tool for splitting tar archives into chunks along file boundaries.")
+    fn split_subcommand() -> clap::App<'a, 'b> {
+        clap::SubCommand::with_name("split")
+            .about("Split a tar archive into chunks along file boundaries.")
             .arg(clap::Arg::with_name("CHUNK_SIZE")
                 .short("c")
                 .long("chunk-size")
                 .takes_value(true)
-                .help("Approximate size of output chunks in bytes (incompatible with NUM_CHUNKS)"))
+                .help("Approximate size of output chunks in bytes, measured before compression \
+                       is applied (incompatible with NUM_CHUNKS)"))
             .arg(clap::Arg::with_name("NUM_CHUNKS")
                 .short("n")
                 .long("num-chunks")
@@ -33,6 +31,39 @@ impl<'a, 'b> Cli<'a, 'b> {
                 .takes_value(true)
                 .default_value("split")
                 .help("Prefix to apply to filename of each output chunk"))
+            .arg(clap::Arg::with_name("COMPRESSION")
+                .long("compression")
+                .takes_value(true)
+                .possible_values(&["none", "gzip", "bzip2", "zstd"])
+                .default_value("none")
+                .help("Transparently decompress the source archive and write output chunks \
+                       compressed with the given format"))
+            .arg(clap::Arg::with_name("MAX_ENTRIES")
+                .long("max-entries")
+                .takes_value(true)
+                .help("Abort if the source archive contains more than this many entries"))
+            .arg(clap::Arg::with_name("MAX_TOTAL_SIZE")
+                .long("max-total-size")
+                .takes_value(true)
+                .help("Abort if the source archive's total uncompressed entry size exceeds this \
+                       many bytes"))
+            .arg(clap::Arg::with_name("CDC")
+                .long("cdc")
+                .help("When an entry must be split across chunks, cut its parts at \
+                       content-defined (FastCDC) boundaries instead of fixed offsets, so that \
+                       inserting bytes only shifts the part(s) touching the insertion"))
+            .arg(clap::Arg::with_name("JOBS")
+                .short("j")
+                .long("jobs")
+                .takes_value(true)
+                .help("Maximum number of worker threads used to serialize, compress, and flush \
+                       completed chunks concurrently (defaults to the number of CPU cores)"))
+            .arg(clap::Arg::with_name("MAX_BUFFERED_BYTES")
+                .long("max-buffered-bytes")
+                .takes_value(true)
+                .help("Cap on the bytes of buffered entry data the reader thread may hand off to \
+                       the worker pool before blocking, bounding memory use when the reader \
+                       outpaces chunk writers"))
             .arg(clap::Arg::with_name("SOURCE")
                 .takes_value(true)
                 .required(true)
@@ -43,6 +74,76 @@ impl<'a, 'b> Cli<'a, 'b> {
                 .help("File output path (directory must exist)"))
     }
 
+    fn tarjoin_subcommand() -> clap::App<'a, 'b> {
+        clap::SubCommand::with_name("tarjoin")
+            .about("Reconstruct the source archive's entries from a set of tarsplit chunks, \
+                    rejoining split parts as regular-file entries. The output is rewritten \
+                    entry by entry and is not guaranteed to be byte-identical to the source.")
+            .arg(clap::Arg::with_name("PREFIX")
+                 .short("p")
+                 .long("prefix")
+                .takes_value(true)
+                .default_value("split")
+                .help("Prefix shared by the chunk filenames to join"))
+            .arg(clap::Arg::with_name("CHUNK_DIR")
+                .takes_value(true)
+                .required(true)
+                .help("Directory containing the chunk files to join"))
+            .arg(clap::Arg::with_name("OUTPUT")
+                .takes_value(true)
+                .required(true)
+                .help("Path to write the reconstructed TAR archive"))
+    }
+
+    fn verify_subcommand() -> clap::App<'a, 'b> {
+        clap::SubCommand::with_name("verify")
+            .about("Verify a chunk set against its manifest, reporting any missing or \
+                    corrupted chunks.")
+            .arg(clap::Arg::with_name("MANIFEST")
+                .takes_value(true)
+                .required(true)
+                .help("Path to the manifest written alongside the chunk files by `split`"))
+            .arg(clap::Arg::with_name("CHUNK_DIR")
+                .takes_value(true)
+                .required(true)
+                .help("Directory containing the chunk files to verify"))
+    }
+
+    fn extract_subcommand() -> clap::App<'a, 'b> {
+        clap::SubCommand::with_name("extract")
+            .about("Extract entries matching a glob pattern from a chunk set, using the \
+                    manifest to open only the chunk(s) that contain them.")
+            .arg(clap::Arg::with_name("MANIFEST")
+                .takes_value(true)
+                .required(true)
+                .help("Path to the manifest written alongside the chunk files by `split`"))
+            .arg(clap::Arg::with_name("CHUNK_DIR")
+                .takes_value(true)
+                .required(true)
+                .help("Directory containing the chunk files to extract from"))
+            .arg(clap::Arg::with_name("ONLY")
+                .takes_value(true)
+                .required(true)
+                .help("Glob pattern matching the entry path(s) to extract"))
+            .arg(clap::Arg::with_name("OUTPUT")
+                .takes_value(true)
+                .required(true)
+                .help("Directory to write extracted entries into (must exist)"))
+    }
+
+    fn initialize_parser() -> clap::App<'a, 'b> {
+        // Command line app
+        clap::App::new("tarsplit")
+            .version(env!("CARGO_PKG_VERSION"))
+            .author("All The Music, LLC")
+            .about("Tool for splitting tar archives into chunks along file boundaries.")
+            .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(Cli::split_subcommand())
+            .subcommand(Cli::tarjoin_subcommand())
+            .subcommand(Cli::verify_subcommand())
+            .subcommand(Cli::extract_subcommand())
+    }
+
     pub fn new() -> Cli<'a, 'b> {
         Cli {
             app: Cli::initialize_parser(),
@@ -51,6 +152,40 @@ impl<'a, 'b> Cli<'a, 'b> {
 
     pub fn run(self) {
         let matches = self.app.get_matches();
-        crate::directives::tarsplit(crate::directives::TarsplitDirectiveArgs::from(&matches));
+        let result = match matches.subcommand() {
+            ("split", Some(sub_matches)) => Cli::execute_split(sub_matches),
+            ("tarjoin", Some(sub_matches)) => Cli::execute_tarjoin(sub_matches),
+            ("verify", Some(sub_matches)) => Cli::execute_verify(sub_matches),
+            ("extract", Some(sub_matches)) => Cli::execute_extract(sub_matches),
+            _ => unreachable!("SubcommandRequiredElseHelp guarantees a subcommand is present"),
+        };
+        if let Err(err) = result {
+            eprintln!("::: ERROR: {}", err);
+            std::process::exit(1);
+        }
+    }
+
+    fn execute_split(matches: &clap::ArgMatches) -> Result<(), crate::directives::TarsplitError> {
+        use std::convert::TryFrom;
+        let args = crate::directives::TarsplitDirectiveArgs::try_from(matches)?;
+        crate::directives::tarsplit(args)
+    }
+
+    fn execute_tarjoin(matches: &clap::ArgMatches) -> Result<(), crate::directives::TarsplitError> {
+        use std::convert::TryFrom;
+        let args = crate::directives::TarjoinDirectiveArgs::try_from(matches)?;
+        crate::directives::tarjoin(args)
+    }
+
+    fn execute_verify(matches: &clap::ArgMatches) -> Result<(), crate::directives::TarsplitError> {
+        use std::convert::TryFrom;
+        let args = crate::directives::VerifyDirectiveArgs::try_from(matches)?;
+        crate::directives::tarsplit_verify(args)
+    }
+
+    fn execute_extract(matches: &clap::ArgMatches) -> Result<(), crate::directives::TarsplitError> {
+        use std::convert::TryFrom;
+        let args = crate::directives::ExtractDirectiveArgs::try_from(matches)?;
+        crate::directives::tarsplit_extract(args)
     }
 }
@@ -6,7 +6,9 @@
 // To view a copy of this license, visit http://creativecommons.org/licenses/by/4.0/ or send
 // a letter to Creative Commons, PO Box 1866, Mountain View, CA 94042, USA.
 
-use std::io::Read;
+use std::convert::TryFrom;
+use std::io::{Read, Write};
+use std::sync::Mutex;
 
 /**************************/
 /***** Main Directive *****/
@@ -16,25 +18,146 @@ use std::io::Read;
 const MIN_ARCHIVE_SIZE: u64 = 1024;
 /// Minimum user-provided chunks number
 const MIN_NUM_CHUNKS: u32 = 2;
+/// Size an entry (plus its header) is assumed to occupy when it is smaller
+/// than a single 512-byte tar block.
+const TAR_BLOCK_SIZE: u64 = 512;
+
+/// Errors surfaced by argument parsing and the `tarsplit` directive itself.
+///
+/// Borrows the `hardened_unpack`-style approach of never panicking on
+/// untrusted input: malformed archives, arguments, and crafted entries are
+/// all reported as a `TarsplitError` rather than aborting the process.
+#[derive(Debug, thiserror::Error)]
+pub enum TarsplitError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Archive error: {0}")]
+    Archive(String),
+    #[error("Limit exceeded: {0}")]
+    LimitExceeded(String),
+    #[error("Size overflow: {0}")]
+    Overflow(String),
+}
+
+/// Compression format applied to the source archive (on read) and each
+/// output chunk (on write). Note that `--chunk-size` is always measured
+/// against *uncompressed* entry bytes, so the resulting chunk files may end
+/// up smaller than the requested budget once compressed. `--num-chunks`, by
+/// contrast, divides the *compressed*, on-disk source file size (the true
+/// uncompressed total isn't known until the archive is read), so it cannot
+/// produce an accurate per-chunk budget against a compressed source and is
+/// rejected together with one; pass `--chunk-size` instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Bzip2,
+    Zstd,
+}
+
+impl Compression {
+    fn from_arg(compression: &str) -> Result<Compression, TarsplitError> {
+        match compression {
+            "none" => Ok(Compression::None),
+            "gzip" => Ok(Compression::Gzip),
+            "bzip2" => Ok(Compression::Bzip2),
+            "zstd" => Ok(Compression::Zstd),
+            _ => Err(TarsplitError::Archive(format!("Unrecognized compression format: {}", compression))),
+        }
+    }
+
+    /// Filename suffix appended after `.tar` for each output chunk.
+    fn extension(&self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => ".gz",
+            Compression::Bzip2 => ".bz2",
+            Compression::Zstd => ".zst",
+        }
+    }
+}
+
+/// Format version of the manifest written alongside a chunk set, bumped if
+/// the on-disk schema changes in an incompatible way.
+const MANIFEST_FORMAT_VERSION: u32 = 1;
+
+/// Sidecar table-of-contents written next to a chunk set, recording enough
+/// per-chunk metadata (filename, size, contained entries, CRC32) that
+/// `verify` can check chunk integrity and `extract` can open only the
+/// chunk(s) containing a wanted entry.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub source_size: u64,
+    pub chunks: Vec<ManifestChunk>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ManifestChunk {
+    pub filename: String,
+    pub byte_length: u64,
+    pub entries: Vec<String>,
+    pub crc32: u32,
+}
+
+impl Manifest {
+    /// Filename of the manifest sidecar for a given prefix/filename_base,
+    /// matching the convention `gen_chunk_filename` uses for chunks.
+    fn filename(prefix: &str, filename_base: &str) -> String {
+        format!("{}_{}.manifest.json", prefix, filename_base)
+    }
+
+    fn write(&self, target: &std::path::Path, prefix: &str, filename_base: &str) -> Result<(), TarsplitError> {
+        let path = target.join(Manifest::filename(prefix, filename_base));
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer_pretty(std::io::BufWriter::new(file), self)
+            .map_err(|e| TarsplitError::Archive(format!("Failed to write manifest: {}", e)))
+    }
+
+    fn load(path: &std::path::Path) -> Result<Manifest, TarsplitError> {
+        let file = std::fs::File::open(path)?;
+        serde_json::from_reader(std::io::BufReader::new(file))
+            .map_err(|e| TarsplitError::Archive(format!("Failed to parse manifest {}: {}", path.display(), e)))
+    }
+}
+
+/// Computes the byte length and CRC32 of a just-written chunk file, for
+/// recording in the manifest.
+#[doc(hidden)]
+fn hash_chunk_file(path: &std::path::Path) -> Result<(u64, u32), TarsplitError> {
+    let bytes = std::fs::read(path)?;
+    Ok((bytes.len() as u64, crc32fast::hash(&bytes)))
+}
 
 #[derive(Debug)]
 pub struct TarsplitDirectiveArgs {
     pub chunk_size: Option<u64>,
     pub num_chunks: Option<u32>,
     pub prefix: String,
+    pub compression: Compression,
+    pub max_entries: Option<u64>,
+    pub max_total_size: Option<u64>,
+    pub cdc: bool,
+    pub jobs: Option<usize>,
+    pub max_buffered_bytes: Option<u64>,
     pub source: String,
     pub target: String,
 }
 
-impl<'a> From<&clap::ArgMatches<'a>> for TarsplitDirectiveArgs {
-    fn from(matches: &clap::ArgMatches<'a>) -> TarsplitDirectiveArgs {
+impl<'a> TryFrom<&clap::ArgMatches<'a>> for TarsplitDirectiveArgs {
+    type Error = TarsplitError;
+
+    fn try_from(matches: &clap::ArgMatches<'a>) -> Result<TarsplitDirectiveArgs, TarsplitError> {
         // Parse chunk size argument
         let chunk_size = match matches.value_of("CHUNK_SIZE") {
             None => None,
             Some(chunk_size) => {
-                let chunk_size = chunk_size.parse::<u64>().unwrap();
+                let chunk_size = chunk_size.parse::<u64>()
+                    .map_err(|e| TarsplitError::Archive(format!("Invalid chunk size: {}", e)))?;
                 if chunk_size < MIN_ARCHIVE_SIZE {
-                    panic!("Chunk size must be at least {}", MIN_ARCHIVE_SIZE);
+                    return Err(TarsplitError::Archive(format!(
+                        "Chunk size must be at least {}", MIN_ARCHIVE_SIZE
+                    )));
                 }
                 Some(chunk_size)
             },
@@ -42,14 +165,19 @@ impl<'a> From<&clap::ArgMatches<'a>> for TarsplitDirectiveArgs {
         // Parse number of chunks argument
         let num_chunks = match matches.value_of("NUM_CHUNKS") {
             None => {
-                if chunk_size == None {
-                    panic!("Must provide either chunk size or number of chunks");
+                if chunk_size.is_none() {
+                    return Err(TarsplitError::Archive(
+                        "Must provide either chunk size or number of chunks".to_string()
+                    ));
                 } else { None }
             },
             Some(num_chunks) => {
-                let num_chunks = num_chunks.parse::<u32>().unwrap();
+                let num_chunks = num_chunks.parse::<u32>()
+                    .map_err(|e| TarsplitError::Archive(format!("Invalid number of chunks: {}", e)))?;
                 if num_chunks == MIN_NUM_CHUNKS {
-                    panic!("Number of chunks must be greater than {}", MIN_NUM_CHUNKS);
+                    return Err(TarsplitError::Archive(format!(
+                        "Number of chunks must be greater than {}", MIN_NUM_CHUNKS
+                    )));
                 }
                 Some(num_chunks)
             },
@@ -59,157 +187,1200 @@ impl<'a> From<&clap::ArgMatches<'a>> for TarsplitDirectiveArgs {
             None => String::from("split"),
             Some(prefix) => String::from(prefix),
         };
+        // Parse compression argument
+        let compression = Compression::from_arg(matches.value_of("COMPRESSION").unwrap_or("none"))?;
+        // --num-chunks divides the compressed, on-disk source size; against
+        // a compressed source that bears no fixed relationship to the
+        // uncompressed entry bytes the budget is actually compared against,
+        // so the combination can't produce the requested chunk count or size.
+        if compression != Compression::None && num_chunks.is_some() {
+            return Err(TarsplitError::Archive(
+                "--num-chunks cannot be combined with --compression (the uncompressed source \
+                 size isn't known until the archive is read, so no accurate per-chunk budget \
+                 can be computed); use --chunk-size instead".to_string()
+            ));
+        }
+        // Parse maximum entries guardrail
+        let max_entries = match matches.value_of("MAX_ENTRIES") {
+            None => None,
+            Some(max_entries) => Some(max_entries.parse::<u64>()
+                .map_err(|e| TarsplitError::Archive(format!("Invalid max entries: {}", e)))?),
+        };
+        // Parse maximum total (uncompressed) size guardrail
+        let max_total_size = match matches.value_of("MAX_TOTAL_SIZE") {
+            None => None,
+            Some(max_total_size) => Some(max_total_size.parse::<u64>()
+                .map_err(|e| TarsplitError::Archive(format!("Invalid max total size: {}", e)))?),
+        };
+        // Parse the content-defined chunking flag
+        let cdc = matches.is_present("CDC");
+        // Parse worker pool size
+        let jobs = match matches.value_of("JOBS") {
+            None => None,
+            Some(jobs) => {
+                let jobs = jobs.parse::<usize>()
+                    .map_err(|e| TarsplitError::Archive(format!("Invalid jobs: {}", e)))?;
+                if jobs == 0 {
+                    return Err(TarsplitError::Archive("Jobs must be greater than 0".to_string()));
+                }
+                Some(jobs)
+            },
+        };
+        // Parse the reader's in-flight buffering cap
+        let max_buffered_bytes = match matches.value_of("MAX_BUFFERED_BYTES") {
+            None => None,
+            Some(max_buffered_bytes) => Some(max_buffered_bytes.parse::<u64>()
+                .map_err(|e| TarsplitError::Archive(format!("Invalid max buffered bytes: {}", e)))?),
+        };
         // Parse source argument
         let source = matches.value_of("SOURCE").unwrap().to_string();
         // Parse target path argument
         let target  = matches.value_of("TARGET").unwrap().to_string();
 
-        TarsplitDirectiveArgs {
+        Ok(TarsplitDirectiveArgs {
             chunk_size,
             num_chunks,
             prefix,
+            compression,
+            max_entries,
+            max_total_size,
+            cdc,
+            jobs,
+            max_buffered_bytes,
             source,
             target,
-        }
+        })
     }
 }
 
 #[doc(hidden)]
-fn gen_chunk_size(chunk_size: &Option<u64>, num_chunks: &Option<u32>, source_size: &u64) -> u64 {
+fn gen_chunk_size(chunk_size: &Option<u64>, num_chunks: &Option<u32>, source_size: &u64) -> Result<u64, TarsplitError> {
     // Calculate output chunks (maximum) size
     match chunk_size {
         // If num_chunks specified, calculate maximum size of each chunk
         // as source_size / num_chunks
         None => {
             let max_chunk_size = ((*source_size as f64) / (num_chunks.unwrap() as f64)).round() as u64;
-            // Panic if chunk size is zero
+            // Error if chunk size is below the minimum archive size
             if max_chunk_size < MIN_ARCHIVE_SIZE {
-                panic!("Calculated chunk size must be at least {} bytes, try providing \
-                       a lower number of chunks (<{})", MIN_ARCHIVE_SIZE, num_chunks.unwrap());
+                return Err(TarsplitError::Archive(format!(
+                    "Calculated chunk size must be at least {} bytes, try providing \
+                    a lower number of chunks (<{})", MIN_ARCHIVE_SIZE, num_chunks.unwrap()
+                )));
             }
-            max_chunk_size
+            Ok(max_chunk_size)
         },
         // Otherwise use the user-provided chunk size
         Some(max_chunk_size) => {
-            // Panic if chunk_size greater than size of source archive
+            // Error if chunk_size greater than size of source archive
             if max_chunk_size >= source_size {
-                panic!(
+                return Err(TarsplitError::Archive(format!(
                     "Chunk size must be less than source archive size ({} >= {})",
                     max_chunk_size,
                     source_size
-                );
+                )));
             }
-            *max_chunk_size
+            Ok(*max_chunk_size)
         },
     }
 }
 
+/// Adds a 512-byte header plus the entry size (counted as at least one
+/// 512-byte block, not rounded up to a multiple of 512) for a newly appended
+/// entry to `current_chunk_size`, using checked arithmetic so a crafted
+/// archive with an implausible entry size cannot silently wrap the
+/// accumulator instead of tripping the size-budget check.
 #[doc(hidden)]
-fn gen_chunk_filename(prefix: &str, filename_base: &str, chunk_count: u32) -> String {
-    format!("{}_{}_{}.tar", prefix, filename_base, chunk_count)
+fn checked_total_size_sum(current_chunk_size: u64, entry_size: u64) -> Result<u64, TarsplitError> {
+    let aligned_entry_size = if entry_size > TAR_BLOCK_SIZE { entry_size } else { TAR_BLOCK_SIZE };
+    current_chunk_size
+        .checked_add(TAR_BLOCK_SIZE)
+        .and_then(|sum| sum.checked_add(aligned_entry_size))
+        .ok_or_else(|| TarsplitError::Overflow(format!(
+            "Accumulated chunk size overflowed adding a {} byte entry to a {} byte chunk",
+            entry_size, current_chunk_size
+        )))
 }
 
+/// Rejects entry paths that are absolute or that climb out of the current
+/// directory via a `..` component, so a malicious source archive cannot use
+/// a crafted entry path to drive output outside of `TARGET`.
+#[doc(hidden)]
+fn sanitize_entry_path(entry_path: &std::path::Path) -> Result<(), TarsplitError> {
+    if entry_path.is_absolute() {
+        return Err(TarsplitError::Archive(format!(
+            "Entry path {} is absolute", entry_path.display()
+        )));
+    }
+    if entry_path.components().any(|c| c == std::path::Component::ParentDir) {
+        return Err(TarsplitError::Archive(format!(
+            "Entry path {} contains a parent directory (..) component", entry_path.display()
+        )));
+    }
+    Ok(())
+}
+
+#[doc(hidden)]
+fn gen_chunk_filename(prefix: &str, filename_base: &str, chunk_count: u32, compression: Compression) -> String {
+    format!("{}_{}_{}.tar{}", prefix, filename_base, chunk_count, compression.extension())
+}
+
+/// Wraps the `BufWriter<File>` of an output chunk with the encoder matching
+/// the requested `Compression`, so every chunk is written in the same
+/// format the source archive will eventually be read back in.
+#[doc(hidden)]
+enum ChunkWriter {
+    None(std::io::BufWriter<std::fs::File>),
+    Gzip(flate2::write::GzEncoder<std::io::BufWriter<std::fs::File>>),
+    Bzip2(bzip2::write::BzEncoder<std::io::BufWriter<std::fs::File>>),
+    Zstd(zstd::Encoder<'static, std::io::BufWriter<std::fs::File>>),
+}
+
+impl ChunkWriter {
+    fn new(file: std::fs::File, compression: Compression) -> std::io::Result<ChunkWriter> {
+        let file = std::io::BufWriter::new(file);
+        Ok(match compression {
+            Compression::None => ChunkWriter::None(file),
+            Compression::Gzip => ChunkWriter::Gzip(
+                flate2::write::GzEncoder::new(file, flate2::Compression::default())
+            ),
+            Compression::Bzip2 => ChunkWriter::Bzip2(
+                bzip2::write::BzEncoder::new(file, bzip2::Compression::default())
+            ),
+            Compression::Zstd => ChunkWriter::Zstd(
+                zstd::Encoder::new(file, 0)?
+            ),
+        })
+    }
+
+    /// Flushes any buffered output and, for compressed formats, writes the
+    /// trailing footer (e.g. the gzip CRC/size trailer) required to produce
+    /// a valid archive.
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            ChunkWriter::None(mut file) => file.flush(),
+            ChunkWriter::Gzip(encoder) => encoder.finish().map(|_| ()),
+            ChunkWriter::Bzip2(encoder) => encoder.finish().map(|_| ()),
+            ChunkWriter::Zstd(encoder) => encoder.finish().map(|_| ()),
+        }
+    }
+}
+
+impl std::io::Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            ChunkWriter::None(w) => w.write(buf),
+            ChunkWriter::Gzip(w) => w.write(buf),
+            ChunkWriter::Bzip2(w) => w.write(buf),
+            ChunkWriter::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            ChunkWriter::None(w) => w.flush(),
+            ChunkWriter::Gzip(w) => w.flush(),
+            ChunkWriter::Bzip2(w) => w.flush(),
+            ChunkWriter::Zstd(w) => w.flush(),
+        }
+    }
+}
+
+/// Wraps the source archive's `File` with the decoder matching the
+/// requested `Compression`, so `tar::Archive` transparently iterates
+/// entries regardless of how the source was compressed.
+#[doc(hidden)]
+enum SourceReader {
+    None(std::io::BufReader<std::fs::File>),
+    Gzip(flate2::read::GzDecoder<std::io::BufReader<std::fs::File>>),
+    Bzip2(bzip2::read::BzDecoder<std::io::BufReader<std::fs::File>>),
+    Zstd(zstd::Decoder<'static, std::io::BufReader<std::fs::File>>),
+}
+
+impl SourceReader {
+    fn new(file: std::fs::File, compression: Compression) -> std::io::Result<SourceReader> {
+        let file = std::io::BufReader::new(file);
+        Ok(match compression {
+            Compression::None => SourceReader::None(file),
+            Compression::Gzip => SourceReader::Gzip(flate2::read::GzDecoder::new(file)),
+            Compression::Bzip2 => SourceReader::Bzip2(bzip2::read::BzDecoder::new(file)),
+            Compression::Zstd => SourceReader::Zstd(zstd::Decoder::with_buffer(file)?),
+        })
+    }
+}
+
+impl std::io::Read for SourceReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            SourceReader::None(r) => r.read(buf),
+            SourceReader::Gzip(r) => r.read(buf),
+            SourceReader::Bzip2(r) => r.read(buf),
+            SourceReader::Zstd(r) => r.read(buf),
+        }
+    }
+}
+
+/// Writes the tar end-of-archive marker and, for compressed chunks, the
+/// encoder's trailing footer (e.g. the gzip CRC/size trailer).
+#[doc(hidden)]
+fn finish_chunk_archive(mut archive_chunk: tar::Builder<ChunkWriter>) -> Result<(), TarsplitError> {
+    archive_chunk.finish()?;
+    archive_chunk.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Opens a new chunk archive, returning it alongside the filename it was
+/// created under so the caller can track it in the manifest.
 #[doc(hidden)]
 fn gen_chunk_archive(
     target: &std::path::Path,
     prefix: &str,
     filename_base: &str,
-    chunk_count: u32
-) -> tar::Builder<std::io::BufWriter<std::fs::File>> {
-    let filepath = gen_chunk_filename(prefix, filename_base, chunk_count);
-    let filepath = target.join(&filepath);
-    tar::Builder::new(
-        std::io::BufWriter::new(
-            std::fs::File::create(filepath.as_path()).unwrap()
-        )
-    )
-}
-
-pub fn tarsplit(args: TarsplitDirectiveArgs) {
+    chunk_count: u32,
+    compression: Compression,
+) -> Result<(tar::Builder<ChunkWriter>, String), TarsplitError> {
+    let filename = gen_chunk_filename(prefix, filename_base, chunk_count, compression);
+    let filepath = target.join(&filename);
+    let archive_chunk = tar::Builder::new(
+        ChunkWriter::new(std::fs::File::create(filepath.as_path())?, compression)?
+    );
+    Ok((archive_chunk, filename))
+}
+
+/// Filename suffix format for a sub-split part. Parts are zero-padded so
+/// that lexical and numeric ordering agree.
+fn part_suffix(part_index: u32) -> String {
+    format!(".part{:04}", part_index)
+}
+
+/// Tar type flag marking a sub-split part entry written by
+/// `write_oversized_entry`. Reassembly gates on this instead of the
+/// `<path>.partNNNN` filename alone, so a whole source entry that happens to
+/// be named like a part (e.g. `data.part0001`) can't be misdetected and
+/// corrupted on `tarjoin`. Must be a byte the `tar` crate doesn't special-case
+/// on read (it specially parses `'0'..='7'`, `'x'`, `'g'`, `'L'`, `'K'`, and
+/// `'S'` as GNU/PAX headers rather than passing the entry through as plain
+/// data) — `'P'` falls through to `EntryType::__Nonexhaustive` and round-trips
+/// untouched.
+const PART_ENTRY_TYPE: u8 = b'P';
+
+/// Bounds how many bytes of entry data the reader thread may have buffered
+/// and handed off to the worker pool but not yet flushed to disk, so a
+/// reader racing ahead of slow (e.g. highly compressed) chunk writers can't
+/// grow memory use without limit. `max: None` leaves buffering unbounded.
+#[doc(hidden)]
+struct BufferBudget {
+    max: Option<u64>,
+    held: Mutex<u64>,
+    available: std::sync::Condvar,
+}
+
+impl BufferBudget {
+    fn new(max: Option<u64>) -> BufferBudget {
+        BufferBudget { max, held: Mutex::new(0), available: std::sync::Condvar::new() }
+    }
+
+    /// Blocks until `bytes` can be added to the outstanding total without
+    /// exceeding `max`, then reserves them. A bin larger than `max` is
+    /// still admitted once the budget is otherwise empty, so a single
+    /// oversized bin can't deadlock the pipeline.
+    fn reserve(&self, bytes: u64) {
+        let max = match self.max {
+            Some(max) => max,
+            None => return,
+        };
+        let mut held = self.held.lock().unwrap();
+        while *held > 0 && *held + bytes > max {
+            held = self.available.wait(held).unwrap();
+        }
+        *held += bytes;
+    }
+
+    /// Releases `bytes` reserved by an earlier `reserve`, waking any reader
+    /// blocked waiting for room.
+    fn release(&self, bytes: u64) {
+        if self.max.is_none() {
+            return;
+        }
+        let mut held = self.held.lock().unwrap();
+        *held = held.saturating_sub(bytes);
+        self.available.notify_all();
+    }
+}
+
+/// One entry's (or sub-split part's) bytes, read fully into memory by the
+/// reader thread so ownership can cross over to a worker thread, which
+/// streams them into a chunk archive at its own pace.
+#[doc(hidden)]
+struct BufferedEntry {
+    header: tar::Header,
+    // Kept as a PathBuf (not a lossily-converted String) so a non-UTF-8
+    // entry name round-trips through tarsplit/tarjoin byte-for-byte.
+    path: std::path::PathBuf,
+    data: Vec<u8>,
+}
+
+/// One chunk archive's worth of buffered entries, assigned by the reader
+/// thread's greedy size-boundary logic and handed whole to a worker thread
+/// to serialize, compress, and flush.
+#[doc(hidden)]
+struct ChunkBin {
+    chunk_count: u32,
+    entries: Vec<BufferedEntry>,
+}
+
+/// Everything a worker thread needs to turn a `ChunkBin` into a chunk file
+/// and record it in the manifest, shared (by reference) across every
+/// spawned task for the run.
+#[doc(hidden)]
+struct WorkerContext<'a> {
+    target: &'a std::path::Path,
+    prefix: &'a str,
+    filename_base: &'a str,
+    compression: Compression,
+    budget: &'a BufferBudget,
+    manifest_slots: &'a Mutex<std::collections::BTreeMap<u32, ManifestChunk>>,
+    /// First error raised by any worker; the reader thread checks this once
+    /// all chunks have been dispatched.
+    error: &'a Mutex<Option<TarsplitError>>,
+}
+
+/// The fixed parameters needed to dispatch a completed `ChunkBin` to the
+/// worker pool, bundled so `write_oversized_entry` doesn't have to take each
+/// one individually.
+#[doc(hidden)]
+struct Dispatcher<'s, 'scope, 'w: 'scope> {
+    /// When set, `write_oversized_entry` cuts an oversized entry's parts at
+    /// content-defined (FastCDC) boundaries instead of fixed offsets.
+    cdc: bool,
+    // `rayon::Scope<'scope>` is invariant over `'scope`, so it needs its own
+    // lifetime parameters distinct from `wctx`'s: `'s` is merely how long
+    // we hold this reference to the scope (the body of the `pool.scope`
+    // closure), while `'w: 'scope` reflects that `wctx`, once captured by a
+    // `scope.spawn` closure, must actually outlive the scope itself.
+    scope: &'s rayon::Scope<'scope>,
+    wctx: &'w WorkerContext<'w>,
+}
+
+/// The `tarsplit` reader loop's mutable chunk-assignment state, threaded
+/// through `write_oversized_entry` and handed back so the caller can resume
+/// its own loop from where the sub-split left off.
+#[doc(hidden)]
+struct BinProgress {
+    current_bin: ChunkBin,
+    current_bin_size: u64,
+}
+
+/// Serializes a completed bin into its chunk archive, compresses and
+/// flushes it, and hashes the result for the manifest. Runs on a worker
+/// thread, in parallel with the reader assigning later bins and with other
+/// workers writing their own bins.
+#[doc(hidden)]
+fn write_bin(bin: ChunkBin, wctx: &WorkerContext) -> Result<ManifestChunk, TarsplitError> {
+    println!("::: INFO: Writing chunk {}", bin.chunk_count);
+    let (mut archive_chunk, filename) = gen_chunk_archive(
+        wctx.target, wctx.prefix, wctx.filename_base, bin.chunk_count, wctx.compression
+    )?;
+
+    let mut entries = Vec::with_capacity(bin.entries.len());
+    for entry in bin.entries {
+        let mut header = entry.header;
+        archive_chunk.append_data(&mut header, &entry.path, entry.data.as_slice())?;
+        // The manifest's entries list is display/matching metadata only (see
+        // `tarsplit_extract`'s glob filter), not what reconstruction relies
+        // on, so a lossy conversion here is fine even for non-UTF-8 paths.
+        entries.push(entry.path.to_string_lossy().into_owned());
+    }
+    finish_chunk_archive(archive_chunk)?;
+
+    let chunk_path = wctx.target.join(&filename);
+    let (byte_length, crc32) = hash_chunk_file(&chunk_path)?;
+    Ok(ManifestChunk { filename, byte_length, entries, crc32 })
+}
+
+/// Hands the current bin to the worker pool to be written in the
+/// background and returns progress reset onto a fresh, empty bin numbered
+/// right after it, so chunk numbering stays deterministic regardless of the
+/// order in which workers actually finish.
+#[doc(hidden)]
+fn advance_bin<'s, 'scope, 'w: 'scope>(progress: BinProgress, dispatcher: &Dispatcher<'s, 'scope, 'w>) -> BinProgress {
+    println!("::: INFO: Reached chunk boundary, dispatching chunk {}", progress.current_bin.chunk_count);
+    let bin = progress.current_bin;
+    let chunk_count = bin.chunk_count;
+    let buffered_bytes: u64 = bin.entries.iter().map(|entry| entry.data.len() as u64).sum();
+    let wctx = dispatcher.wctx;
+
+    // Only bytes already handed off to workers count against the budget, so
+    // the reader never blocks on bytes that it alone still holds.
+    wctx.budget.reserve(buffered_bytes);
+    dispatcher.scope.spawn(move |_| {
+        match write_bin(bin, wctx) {
+            Ok(chunk) => {
+                wctx.manifest_slots.lock().unwrap().insert(chunk_count, chunk);
+            },
+            Err(err) => {
+                let mut error = wctx.error.lock().unwrap();
+                if error.is_none() {
+                    *error = Some(err);
+                }
+            },
+        }
+        // The bin's bytes have been written (or abandoned on error); make
+        // room for the reader to buffer more.
+        wctx.budget.release(buffered_bytes);
+    });
+
+    BinProgress {
+        current_bin: ChunkBin { chunk_count: chunk_count + 1, entries: Vec::new() },
+        current_bin_size: 0,
+    }
+}
+
+/// Deterministic pseudo-random GEAR table used by the `--cdc` FastCDC
+/// rolling fingerprint. Any fixed table works as long as it decorrelates the
+/// fingerprint from the literal byte values; it's generated once at runtime
+/// (seeded with a fixed constant via SplitMix64) rather than hand-written,
+/// so no external RNG dependency is needed, and every run computes the same
+/// table.
+#[doc(hidden)]
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: std::sync::OnceLock<[u64; 256]> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut seed: u64 = 0x9E3779B97F4A7C15;
+        let mut table = [0u64; 256];
+        for slot in table.iter_mut() {
+            seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^= z >> 31;
+            *slot = z;
+        }
+        table
+    })
+}
+
+/// FastCDC-style normalized chunking: cuts are declared when the rolling
+/// Gear fingerprint satisfies `fp & mask == 0`, using a stricter `mask_s`
+/// (more one-bits, so cuts are rarer) below the target size and a looser
+/// `mask_l` once past it, so the resulting part sizes cluster around
+/// `target_size` while `min_size`/`max_size` bound the extremes.
+#[doc(hidden)]
+struct Cdc {
+    min_size: u64,
+    max_size: u64,
+    target_size: u64,
+    mask_s: u64,
+    mask_l: u64,
+}
+
+impl Cdc {
+    fn new(chunk_maximum_size: u64) -> Cdc {
+        let max_size = chunk_maximum_size.max(4);
+        let target_size = (max_size / 2).max(1);
+        let min_size = (target_size / 4).max(1);
+        // Roughly log2(target_size), used as the baseline mask width.
+        let bits = 64 - target_size.leading_zeros();
+        let bits_s = bits.saturating_add(2).min(63);
+        let bits_l = bits.saturating_sub(2).max(1);
+        Cdc {
+            min_size,
+            max_size,
+            target_size,
+            mask_s: (1u64 << bits_s) - 1,
+            mask_l: (1u64 << bits_l) - 1,
+        }
+    }
+
+    /// Scans `data` for the first content-defined cut point, forcing a cut
+    /// once `max_size` bytes have been scanned, and returns `data.len()` if
+    /// neither happens within `data`.
+    fn find_cut(&self, data: &[u8]) -> usize {
+        let gear = gear_table();
+        let mut fp: u64 = 0;
+        for (i, &byte) in data.iter().enumerate() {
+            let pos = i as u64 + 1;
+            fp = (fp << 1).wrapping_add(gear[byte as usize]);
+            if pos < self.min_size {
+                continue;
+            }
+            let mask = if pos < self.target_size { self.mask_s } else { self.mask_l };
+            if fp & mask == 0 || pos >= self.max_size {
+                return i + 1;
+            }
+        }
+        data.len()
+    }
+}
+
+/// Tracks how much of an oversized entry remains to be split into parts,
+/// and how: either fixed `chunk_maximum_size` slices (the default) or
+/// variable-length, content-defined slices buffered up to `Cdc::max_size` at
+/// a time when `--cdc` is set.
+#[doc(hidden)]
+enum PartMode {
+    Fixed {
+        remaining: u64,
+        chunk_maximum_size: u64,
+    },
+    Cdc {
+        remaining: u64,
+        leftover: Vec<u8>,
+        cdc: Cdc,
+    },
+}
+
+impl PartMode {
+    fn has_more(&self) -> bool {
+        match self {
+            PartMode::Fixed { remaining, .. } => *remaining > 0,
+            PartMode::Cdc { remaining, leftover, .. } => *remaining > 0 || !leftover.is_empty(),
+        }
+    }
+
+    /// Reads the next part's bytes fully into memory, reading ahead from
+    /// `entry` as needed to find a `Cdc` cut point.
+    fn next_part<R: Read>(&mut self, entry: &mut R) -> Result<Option<Vec<u8>>, TarsplitError> {
+        match self {
+            PartMode::Fixed { remaining, chunk_maximum_size } => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                let part_size = (*remaining).min(*chunk_maximum_size);
+                let mut buffer = vec![0u8; part_size as usize];
+                entry.read_exact(&mut buffer)?;
+                *remaining -= part_size;
+                Ok(Some(buffer))
+            },
+            PartMode::Cdc { remaining, leftover, cdc } => {
+                let mut buffer = std::mem::take(leftover);
+                while (buffer.len() as u64) < cdc.max_size && *remaining > 0 {
+                    let want = (cdc.max_size - buffer.len() as u64).min(*remaining) as usize;
+                    let mut read_buf = vec![0u8; want];
+                    let read = entry.read(&mut read_buf)?;
+                    if read == 0 {
+                        break;
+                    }
+                    buffer.extend_from_slice(&read_buf[..read]);
+                    *remaining -= read as u64;
+                }
+                if buffer.is_empty() {
+                    return Ok(None);
+                }
+                let cut = cdc.find_cut(&buffer);
+                *leftover = buffer.split_off(cut);
+                Ok(Some(buffer))
+            },
+        }
+    }
+}
+
+/// Buffers an entry larger than `chunk_maximum_size` into one or more part
+/// entries (`<path>.part0000`, `<path>.part0001`, ...), each part in a bin of
+/// its own, dispatching every bin but the last part's. Parts are bounded by
+/// `chunk_maximum_size`, either cut at fixed offsets or, under
+/// `dispatcher.cdc`, at content-defined (FastCDC) boundaries. `tarjoin`
+/// reassembles the parts, in order, back into the original entry.
+#[doc(hidden)]
+fn write_oversized_entry<'s, 'scope, 'w: 'scope, R: Read>(
+    mut entry: R,
+    entry_path: &std::path::Path,
+    entry_header: &tar::Header,
+    entry_size: u64,
+    chunk_maximum_size: u64,
+    dispatcher: &Dispatcher<'s, 'scope, 'w>,
+    mut progress: BinProgress,
+) -> Result<BinProgress, TarsplitError> {
+    // Start the first part on a fresh chunk boundary so every part below
+    // can claim the full chunk_maximum_size budget.
+    if progress.current_bin_size > 0 {
+        progress = advance_bin(progress, dispatcher);
+    }
+
+    let mut mode = if dispatcher.cdc {
+        PartMode::Cdc { remaining: entry_size, leftover: Vec::new(), cdc: Cdc::new(chunk_maximum_size) }
+    } else {
+        PartMode::Fixed { remaining: entry_size, chunk_maximum_size }
+    };
+
+    let mut part_index: u32 = 0;
+    let mut last_part_size: u64 = 0;
+    while let Some(data) = mode.next_part(&mut entry)? {
+        let part_size = data.len() as u64;
+        let mut part_header = entry_header.clone();
+        part_header.set_size(part_size);
+        part_header.set_entry_type(tar::EntryType::new(PART_ENTRY_TYPE));
+        part_header.set_cksum();
+        let part_path = format!("{}{}", entry_path.display(), part_suffix(part_index));
+        println!("::: INFO: Splitting oversized entry {} into part {} ({} bytes)",
+                 entry_path.display(), part_path, part_size);
+
+        progress.current_bin.entries.push(BufferedEntry {
+            header: part_header,
+            path: std::path::PathBuf::from(part_path),
+            data,
+        });
+
+        last_part_size = part_size;
+        part_index += 1;
+
+        // Each part gets a chunk to itself; start a fresh one for the remainder.
+        if mode.has_more() {
+            progress = advance_bin(progress, dispatcher);
+        }
+    }
+
+    // The final part may be smaller than chunk_maximum_size, leaving room in
+    // its bin for the entries that follow.
+    progress.current_bin_size = checked_total_size_sum(0, last_part_size)?;
+    Ok(progress)
+}
+
+pub fn tarsplit(args: TarsplitDirectiveArgs) -> Result<(), TarsplitError> {
     // Ensure source is file and exists
     let source = std::path::Path::new(&args.source);
     if !source.is_file() {
-        panic!("Source must point to an existing archive");
+        return Err(TarsplitError::Archive("Source must point to an existing archive".to_string()));
     }
 
     // Ensure target is existing directory
     let target = std::path::Path::new(&args.target);
     if !target.is_dir() {
-        panic!("Target must point to an existing directory");
+        return Err(TarsplitError::Archive("Target must point to an existing directory".to_string()));
     }
 
     // Read size of source archive
-    let source_size = source.metadata().unwrap().len();
-    // Panic if source is less than 
-    if source_size < MIN_ARCHIVE_SIZE { panic!("::: ERROR: Source archive is less than {} bytes", MIN_ARCHIVE_SIZE); }
+    let source_size = source.metadata()?.len();
+    // Error if source is less than the minimum archive size
+    if source_size < MIN_ARCHIVE_SIZE {
+        return Err(TarsplitError::Archive(format!(
+            "Source archive is less than {} bytes", MIN_ARCHIVE_SIZE
+        )));
+    }
     println!("::: INFO: Source archive is {} bytes", source_size);
 
     // Calculate output chunks (maximum) size
-    let chunk_maximum_size = gen_chunk_size(&args.chunk_size, &args.num_chunks, &source_size);
+    let chunk_maximum_size = gen_chunk_size(&args.chunk_size, &args.num_chunks, &source_size)?;
     println!("::: INFO: Maximum chunk size will be {} bytes", chunk_maximum_size);
 
     // Generate output archives base filename from source archive file stem
-    let chunk_filename_base = source.file_stem().unwrap().to_str().unwrap();
+    let chunk_filename_base = source.file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| TarsplitError::Archive(format!(
+            "Source path {} has no usable (UTF-8) file stem to derive chunk filenames from",
+            source.display()
+        )))?;
 
-    // Read source as TAR archive
-    let source = std::fs::File::open(source).unwrap();
+    // Read source as TAR archive, transparently decompressing it first if
+    // a compression format was specified
+    let source = std::fs::File::open(source)?;
+    let source = SourceReader::new(source, args.compression)?;
     let mut source = tar::Archive::new(source);
 
+    // Cap the worker pool at args.jobs, defaulting to the machine's
+    // available parallelism when unset.
+    let resolved_jobs = args.jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+    });
+    // `pool.scope` below runs the reader loop itself on one of the pool's
+    // own worker threads, so with only one job the reader occupies the
+    // pool's only slot: a spawned chunk-writing task can never be scheduled
+    // to run `budget.release`, and the reader deadlocks forever in
+    // `BufferBudget::reserve`'s `Condvar::wait` (a std condvar, which the
+    // rayon scheduler has no way to work around). Bounded buffering is only
+    // safe with a second thread free to actually write chunks concurrently.
+    if args.max_buffered_bytes.is_some() && resolved_jobs < 2 {
+        return Err(TarsplitError::Archive(
+            "--max-buffered-bytes requires at least 2 jobs (the reader thread occupies one \
+             worker slot, so a single job can deadlock waiting on itself to free buffered \
+             bytes); pass --jobs 2 or higher, or drop --max-buffered-bytes".to_string()
+        ));
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(resolved_jobs)
+        .build()
+        .map_err(|e| TarsplitError::Archive(format!("Failed to start worker pool: {}", e)))?;
+
+    let budget = BufferBudget::new(args.max_buffered_bytes);
+    let manifest_slots: Mutex<std::collections::BTreeMap<u32, ManifestChunk>> = Mutex::new(std::collections::BTreeMap::new());
+    let error: Mutex<Option<TarsplitError>> = Mutex::new(None);
+    let wctx = WorkerContext {
+        target,
+        prefix: &args.prefix,
+        filename_base: chunk_filename_base,
+        compression: args.compression,
+        budget: &budget,
+        manifest_slots: &manifest_slots,
+        error: &error,
+    };
+
     // Initialize loop variable state
-    let mut current_chunk_size: u64 = 0;
-    let mut chunk_count: u32 = 0;
-    let mut archive_chunk = gen_chunk_archive(
-        &target,
-        &args.prefix,
-        chunk_filename_base,
-        chunk_count
-    );
+    let mut total_entries: u64 = 0;
+    let mut total_size: u64 = 0;
+    let mut progress = BinProgress {
+        current_bin: ChunkBin { chunk_count: 0, entries: Vec::new() },
+        current_bin_size: 0,
+    };
 
-    // For each entry in the source archive
-    for entry in source.entries().unwrap() {
-        // Unwrap archive entry
-        let mut entry = entry.unwrap();
-        // Copy header and check entry size
-        let mut entry_header = entry.header().clone();
-        let entry_size = entry_header.entry_size().unwrap();
-
-        // If adding entry would make chunk large than maximum chunk size
-        // TODO: If entry_size itself is larger than chunk_maximum_size,
-        //       write entry alone to separate tar file.
-        if current_chunk_size + entry_size > chunk_maximum_size {
-            println!("::: INFO: Reached chunk boundary, writing chunk {}", chunk_count);
-            // Flush current chunk to disk
-            archive_chunk.finish().unwrap();
-            // Increment chunk count
-            chunk_count = chunk_count + 1;
-            // Generate new archive
-            archive_chunk = gen_chunk_archive(
-                &target,
-                &args.prefix,
-                chunk_filename_base,
-                chunk_count
-            );
-            // Reset current chunk size
-            current_chunk_size = 0;
+    // The reader thread (this one) assigns whole entries to bins using the
+    // same greedy size-boundary logic as the sequential version, then
+    // dispatches each completed bin to `pool`'s workers to serialize,
+    // compress, and flush concurrently. `pool.scope` blocks here until every
+    // dispatched bin has finished.
+    pool.scope(|scope: &rayon::Scope| -> Result<(), TarsplitError> {
+        let dispatcher = Dispatcher { cdc: args.cdc, scope, wctx: &wctx };
+
+        // For each entry in the source archive
+        for entry in source.entries()? {
+            // Unwrap archive entry
+            let mut entry = entry?;
+            // Copy header and check entry size
+            let entry_header = entry.header().clone();
+            let entry_size = entry_header.entry_size()?;
+
+            // Enforce guardrails before processing another entry, so a
+            // crafted or oversized archive is rejected cleanly rather than
+            // exhausting disk space or memory.
+            total_entries += 1;
+            if let Some(max_entries) = args.max_entries {
+                if total_entries > max_entries {
+                    return Err(TarsplitError::LimitExceeded(format!(
+                        "Source archive contains more than the maximum {} entries", max_entries
+                    )));
+                }
+            }
+            total_size = total_size.saturating_add(entry_size);
+            if let Some(max_total_size) = args.max_total_size {
+                if total_size > max_total_size {
+                    return Err(TarsplitError::LimitExceeded(format!(
+                        "Source archive exceeds the maximum total size of {} bytes", max_total_size
+                    )));
+                }
+            }
+
+            // Reject entry paths that could drive output outside of TARGET
+            let entry_path = entry.path()?.to_path_buf();
+            sanitize_entry_path(&entry_path)?;
+
+            // An entry larger than a single chunk can't land whole in one
+            // bin, so split it across consecutive bins as bounded parts.
+            if entry_size > chunk_maximum_size {
+                progress = write_oversized_entry(
+                    entry.by_ref(),
+                    &entry_path,
+                    &entry_header,
+                    entry_size,
+                    chunk_maximum_size,
+                    &dispatcher,
+                    progress,
+                )?;
+                continue;
+            }
+
+            // If adding entry would make the bin larger than the maximum
+            // chunk size, dispatch it and start a fresh one first.
+            if progress.current_bin_size.saturating_add(entry_size) > chunk_maximum_size {
+                progress = advance_bin(progress, &dispatcher);
+            }
+
+            // Buffer the entry's bytes so ownership can cross over to
+            // whichever worker ends up writing this bin.
+            let mut data = vec![0u8; entry_size as usize];
+            entry.read_exact(&mut data)?;
+            progress.current_bin.entries.push(BufferedEntry {
+                header: entry_header,
+                path: entry_path,
+                data,
+            });
+
+            // Increment current bin size by size of header plus entry size
+            // (the entry counted as at least 512 bytes), using checked arithmetic to guard
+            // against a crafted entry size overflowing the accumulator.
+            progress.current_bin_size = checked_total_size_sum(progress.current_bin_size, entry_size)?;
         }
 
-        // Extract entry path
-        let entry_path  = entry.path().unwrap().to_path_buf();
-        // Add entry to archive chunk
-        archive_chunk.append_data(
-            &mut entry_header,
-            entry_path,
-            entry.by_ref()
-        ).unwrap();
+        // Dispatch the final (possibly partial) bin.
+        advance_bin(progress, &dispatcher);
+        Ok(())
+    })?;
+
+    if let Some(err) = error.into_inner().unwrap() {
+        return Err(err);
+    }
+
+    // Write the sidecar manifest alongside the chunk files, in deterministic
+    // chunk-number order regardless of the order workers finished in.
+    let manifest_chunks: Vec<ManifestChunk> = manifest_slots.into_inner().unwrap().into_values().collect();
+    let manifest = Manifest {
+        format_version: MANIFEST_FORMAT_VERSION,
+        source_size,
+        chunks: manifest_chunks,
+    };
+    manifest.write(target, &args.prefix, chunk_filename_base)?;
+
+    Ok(())
+}
+
+/*************************/
+/***** Join Directive ****/
+/*************************/
+
+#[derive(Debug)]
+pub struct TarjoinDirectiveArgs {
+    pub chunk_dir: String,
+    pub prefix: String,
+    pub output: String,
+}
+
+impl<'a> TryFrom<&clap::ArgMatches<'a>> for TarjoinDirectiveArgs {
+    type Error = TarsplitError;
+
+    fn try_from(matches: &clap::ArgMatches<'a>) -> Result<TarjoinDirectiveArgs, TarsplitError> {
+        let chunk_dir = matches.value_of("CHUNK_DIR").unwrap().to_string();
+        let prefix = match matches.value_of("PREFIX") {
+            None => String::from("split"),
+            Some(prefix) => String::from(prefix),
+        };
+        let output = matches.value_of("OUTPUT").unwrap().to_string();
+
+        Ok(TarjoinDirectiveArgs { chunk_dir, prefix, output })
+    }
+}
+
+/// Infers the `Compression` a chunk file was written with from its filename
+/// extension, mirroring the suffixes `gen_chunk_filename` appends.
+#[doc(hidden)]
+fn compression_from_filename(filename: &str) -> Compression {
+    if filename.ends_with(".tar.gz") {
+        Compression::Gzip
+    } else if filename.ends_with(".tar.bz2") {
+        Compression::Bzip2
+    } else if filename.ends_with(".tar.zst") {
+        Compression::Zstd
+    } else {
+        Compression::None
+    }
+}
+
+/// Parses a chunk's `chunk_count` out of a filename produced by
+/// `gen_chunk_filename`, i.e. `<prefix>_<filename_base>_<chunk_count>.tar<ext>`.
+#[doc(hidden)]
+fn parse_chunk_count(filename: &str, prefix: &str) -> Option<u32> {
+    let rest = filename.strip_prefix(prefix)?.strip_prefix('_')?;
+    let tar_pos = rest.rfind(".tar")?;
+    let (stem, _extension) = rest.split_at(tar_pos);
+    let (_filename_base, chunk_count) = stem.rsplit_once('_')?;
+    chunk_count.parse::<u32>().ok()
+}
+
+/// Splits a reconstructed entry's path back into its original path and part
+/// index if it is a sub-split part (`<path>.partNNNN`), or `None` otherwise.
+#[doc(hidden)]
+fn parse_part_path(path: &str) -> Option<(String, u32)> {
+    let part_pos = path.rfind(".part")?;
+    let (original_path, suffix) = path.split_at(part_pos);
+    let part_index = suffix.trim_start_matches(".part").parse::<u32>().ok()?;
+    Some((original_path.to_string(), part_index))
+}
+
+/// Buffers consecutive `.partNNNN` entries sharing one original path so
+/// they can be flushed back into a single reconstructed entry.
+#[doc(hidden)]
+struct PartGroup {
+    original_path: String,
+    header: tar::Header,
+    next_part_index: u32,
+    buffer: Vec<u8>,
+}
+
+/// Discovers the chunk files belonging to `prefix` in `chunk_dir` and sorts
+/// them by chunk count (not lexically, since counts aren't zero-padded).
+#[doc(hidden)]
+fn discover_chunk_files(chunk_dir: &std::path::Path, prefix: &str) -> Result<Vec<std::path::PathBuf>, TarsplitError> {
+    let mut chunks: Vec<(u32, std::path::PathBuf)> = std::fs::read_dir(chunk_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let filename = path.file_name()?.to_str()?;
+            let chunk_count = parse_chunk_count(filename, prefix)?;
+            Some((chunk_count, path))
+        })
+        .collect();
+    if chunks.is_empty() {
+        return Err(TarsplitError::Archive(format!(
+            "No chunk files found in {} with prefix {}", chunk_dir.display(), prefix
+        )));
+    }
+    chunks.sort_by_key(|(chunk_count, _)| *chunk_count);
+    Ok(chunks.into_iter().map(|(_, path)| path).collect())
+}
+
+/// A reconstructed entry handed to `reassemble_entries`' callback: either a
+/// normal entry streamed directly from its chunk, or the buffered result of
+/// concatenating a run of `.partNNNN` entries.
+#[doc(hidden)]
+enum ReassembledEntry<'a> {
+    Whole(&'a mut dyn Read),
+    Parts(Vec<u8>),
+}
+
+/// Iterates every entry across `chunks` in order, concatenating any
+/// `.partNNNN` runs back into their original entry, and invokes `on_entry`
+/// once per reconstructed entry. Shared by `tarjoin` (writes every entry to
+/// one output archive) and the `extract` directive (writes only entries
+/// matching a glob to a directory).
+#[doc(hidden)]
+fn reassemble_entries<F>(chunks: &[std::path::PathBuf], mut on_entry: F) -> Result<(), TarsplitError>
+where
+    F: FnMut(&tar::Header, &str, ReassembledEntry) -> Result<(), TarsplitError>,
+{
+    // Parts of an oversized entry are written out contiguously, so a single
+    // in-flight group is all that's needed to reassemble them in order.
+    let mut part_group: Option<PartGroup> = None;
+
+    for chunk_path in chunks {
+        let filename = chunk_path.file_name().unwrap().to_str().unwrap();
+        let compression = compression_from_filename(filename);
+        let chunk_file = std::fs::File::open(chunk_path)?;
+        let chunk_file = SourceReader::new(chunk_file, compression)?;
+        let mut chunk_archive = tar::Archive::new(chunk_file);
+
+        for entry in chunk_archive.entries()? {
+            let mut entry = entry?;
+            let entry_header = entry.header().clone();
+            let entry_path = entry.path()?.to_path_buf();
+            let entry_path = entry_path.to_string_lossy().into_owned();
+
+            // Only an entry actually written by write_oversized_entry carries
+            // PART_ENTRY_TYPE; a whole entry that merely happens to be named
+            // like a part (e.g. `data.part0001`) keeps its original type and
+            // falls through to the `Whole` arm below untouched.
+            if entry_header.entry_type() == tar::EntryType::new(PART_ENTRY_TYPE) {
+                let (original_path, part_index) = parse_part_path(&entry_path).ok_or_else(|| {
+                    TarsplitError::Archive(format!(
+                        "Chunk entry {} is marked as a split part but its name doesn't match \
+                         the expected <path>.partNNNN pattern", entry_path
+                    ))
+                })?;
+                let mut buffer = Vec::new();
+                entry.read_to_end(&mut buffer)?;
+
+                let continues_group = part_group.as_ref()
+                    .map(|group| group.original_path == original_path && group.next_part_index == part_index)
+                    .unwrap_or(false);
+
+                if !continues_group {
+                    if let Some(group) = part_group.take() {
+                        on_entry(&group.header, &group.original_path, ReassembledEntry::Parts(group.buffer))?;
+                    }
+                    // Only a regular file's data is ever split into parts, so
+                    // restore that type on the reconstructed entry's header
+                    // in place of the PART_ENTRY_TYPE marker used on disk.
+                    let mut header = entry_header.clone();
+                    header.set_entry_type(tar::EntryType::Regular);
+                    header.set_cksum();
+                    part_group = Some(PartGroup {
+                        original_path: original_path.clone(),
+                        header,
+                        next_part_index: 0,
+                        buffer: Vec::new(),
+                    });
+                }
+
+                let group = part_group.as_mut().unwrap();
+                group.buffer.extend_from_slice(&buffer);
+                group.next_part_index += 1;
+            } else {
+                if let Some(group) = part_group.take() {
+                    on_entry(&group.header, &group.original_path, ReassembledEntry::Parts(group.buffer))?;
+                }
+                on_entry(&entry_header, &entry_path, ReassembledEntry::Whole(entry.by_ref()))?;
+            }
+        }
+    }
 
-        // Increment current chunk size by size of header plus entry size
-        // (each aligned to 512 bytes).
-        current_chunk_size = current_chunk_size + 512 + (if entry_size > 512 {entry_size} else {512});
+    if let Some(group) = part_group.take() {
+        on_entry(&group.header, &group.original_path, ReassembledEntry::Parts(group.buffer))?;
     }
 
-    // Flush final chunk to disk
-    println!("::: INFO: Writing final chunk");
-    archive_chunk.finish().unwrap();
+    Ok(())
+}
+
+pub fn tarjoin(args: TarjoinDirectiveArgs) -> Result<(), TarsplitError> {
+    let chunk_dir = std::path::Path::new(&args.chunk_dir);
+    if !chunk_dir.is_dir() {
+        return Err(TarsplitError::Archive("Chunk directory must point to an existing directory".to_string()));
+    }
+    let chunks = discover_chunk_files(chunk_dir, &args.prefix)?;
+
+    // Prepare the reconstructed output archive
+    let output = std::fs::File::create(&args.output)?;
+    let mut output = tar::Builder::new(std::io::BufWriter::new(output));
+
+    reassemble_entries(&chunks, |header, path, entry| {
+        let mut header = header.clone();
+        match entry {
+            ReassembledEntry::Whole(reader) => {
+                output.append_data(&mut header, path, reader)?;
+            },
+            ReassembledEntry::Parts(buffer) => {
+                header.set_size(buffer.len() as u64);
+                header.set_cksum();
+                output.append_data(&mut header, path, buffer.as_slice())?;
+            },
+        }
+        Ok(())
+    })?;
+
+    output.finish()?;
+    Ok(())
+}
+
+/***************************/
+/***** Verify Directive ****/
+/***************************/
+
+#[derive(Debug)]
+pub struct VerifyDirectiveArgs {
+    pub manifest: String,
+    pub dir: String,
+}
+
+impl<'a> TryFrom<&clap::ArgMatches<'a>> for VerifyDirectiveArgs {
+    type Error = TarsplitError;
+
+    fn try_from(matches: &clap::ArgMatches<'a>) -> Result<VerifyDirectiveArgs, TarsplitError> {
+        let manifest = matches.value_of("MANIFEST").unwrap().to_string();
+        let dir = matches.value_of("CHUNK_DIR").unwrap().to_string();
+
+        Ok(VerifyDirectiveArgs { manifest, dir })
+    }
+}
+
+/// Recomputes each manifest chunk's CRC32 against the chunk files on disk in
+/// `args.dir`, reporting missing chunks and size/CRC32 mismatches.
+pub fn tarsplit_verify(args: VerifyDirectiveArgs) -> Result<(), TarsplitError> {
+    let manifest = Manifest::load(std::path::Path::new(&args.manifest))?;
+
+    let dir = std::path::Path::new(&args.dir);
+    if !dir.is_dir() {
+        return Err(TarsplitError::Archive("Directory must point to an existing directory".to_string()));
+    }
+
+    let mut problems = Vec::new();
+    for chunk in &manifest.chunks {
+        let chunk_path = dir.join(&chunk.filename);
+        if !chunk_path.is_file() {
+            problems.push(format!("{}: missing", chunk.filename));
+            continue;
+        }
+
+        let (byte_length, crc32) = hash_chunk_file(&chunk_path)?;
+        if byte_length != chunk.byte_length {
+            problems.push(format!(
+                "{}: expected {} bytes, found {}", chunk.filename, chunk.byte_length, byte_length
+            ));
+        } else if crc32 != chunk.crc32 {
+            problems.push(format!(
+                "{}: CRC32 mismatch (expected {:08x}, found {:08x})", chunk.filename, chunk.crc32, crc32
+            ));
+        } else {
+            println!("::: INFO: {} OK", chunk.filename);
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(TarsplitError::Archive(format!(
+            "{} of {} chunks failed verification:\n  {}",
+            problems.len(), manifest.chunks.len(), problems.join("\n  ")
+        )));
+    }
+
+    println!("::: INFO: All {} chunks verified OK", manifest.chunks.len());
+    Ok(())
+}
+
+/****************************/
+/***** Extract Directive ****/
+/****************************/
+
+#[derive(Debug)]
+pub struct ExtractDirectiveArgs {
+    pub manifest: String,
+    pub dir: String,
+    pub only: String,
+    pub output: String,
+}
+
+impl<'a> TryFrom<&clap::ArgMatches<'a>> for ExtractDirectiveArgs {
+    type Error = TarsplitError;
+
+    fn try_from(matches: &clap::ArgMatches<'a>) -> Result<ExtractDirectiveArgs, TarsplitError> {
+        let manifest = matches.value_of("MANIFEST").unwrap().to_string();
+        let dir = matches.value_of("CHUNK_DIR").unwrap().to_string();
+        let only = matches.value_of("ONLY").unwrap().to_string();
+        let output = matches.value_of("OUTPUT").unwrap().to_string();
+
+        Ok(ExtractDirectiveArgs { manifest, dir, only, output })
+    }
+}
+
+/// Extracts every entry whose path matches `args.only` (a glob pattern)
+/// directly to files under `args.output`, using the manifest to open only
+/// the chunk(s) that actually contain a matching entry instead of scanning
+/// the whole chunk set.
+pub fn tarsplit_extract(args: ExtractDirectiveArgs) -> Result<(), TarsplitError> {
+    let manifest = Manifest::load(std::path::Path::new(&args.manifest))?;
+    let pattern = glob::Pattern::new(&args.only)
+        .map_err(|e| TarsplitError::Archive(format!("Invalid glob pattern {}: {}", args.only, e)))?;
+
+    let dir = std::path::Path::new(&args.dir);
+    if !dir.is_dir() {
+        return Err(TarsplitError::Archive("Chunk directory must point to an existing directory".to_string()));
+    }
+    let output_dir = std::path::Path::new(&args.output);
+    if !output_dir.is_dir() {
+        return Err(TarsplitError::Archive("Output must point to an existing directory".to_string()));
+    }
+
+    // A chunk is worth opening only if one of its recorded entries (or, for
+    // a sub-split entry, its original path) matches the requested glob.
+    let matching_chunks: Vec<std::path::PathBuf> = manifest.chunks.iter()
+        .filter(|chunk| chunk.entries.iter().any(|entry| {
+            pattern.matches(entry)
+                || parse_part_path(entry).map(|(original, _)| pattern.matches(&original)).unwrap_or(false)
+        }))
+        .map(|chunk| dir.join(&chunk.filename))
+        .collect();
+    if matching_chunks.is_empty() {
+        return Err(TarsplitError::Archive(format!(
+            "No entries in the manifest match {}", args.only
+        )));
+    }
+
+    let mut extracted: u64 = 0;
+    reassemble_entries(&matching_chunks, |_header, path, entry| {
+        if !pattern.matches(path) {
+            return Ok(());
+        }
+        sanitize_entry_path(std::path::Path::new(path))?;
+
+        let out_path = output_dir.join(path);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out_file = std::fs::File::create(&out_path)?;
+        match entry {
+            ReassembledEntry::Whole(reader) => { std::io::copy(reader, &mut out_file)?; },
+            ReassembledEntry::Parts(buffer) => { out_file.write_all(&buffer)?; },
+        }
+        println!("::: INFO: Extracted {}", path);
+        extracted += 1;
+        Ok(())
+    })?;
+
+    println!("::: INFO: Extracted {} entries matching {}", extracted, args.only);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -221,12 +1392,12 @@ mod tests {
     test_suite! {
         name tarsplit_test_suite;
 
-// Panics:
+// Errors:
 //  1) Calculated chunk size rounds to less than MIN_ARCHIVE_SIZE
 //  2) User-provided chunk size greater than source archive size
         fixture fixture_gen_chunk_size(
             expected: u64,
-            should_panic: bool,
+            should_err: bool,
             chunk_size: Option<u64>,
             num_chunks: Option<u32>,
             source_size: u64
@@ -261,19 +1432,154 @@ mod tests {
         }
 
         test test_gen_chunk_size(fixture_gen_chunk_size) {
-            if *fixture_gen_chunk_size.params.should_panic {
-                assert_that!(crate::directives::gen_chunk_size(
-                    &fixture_gen_chunk_size.params.chunk_size,
-                    &fixture_gen_chunk_size.params.num_chunks,
-                    &fixture_gen_chunk_size.params.source_size,
-                ), panics);
+            let result = crate::directives::gen_chunk_size(
+                &fixture_gen_chunk_size.params.chunk_size,
+                &fixture_gen_chunk_size.params.num_chunks,
+                &fixture_gen_chunk_size.params.source_size,
+            );
+            if *fixture_gen_chunk_size.params.should_err {
+                assert_that!(result.is_err(), eq true);
             } else {
-                assert_eq!(*fixture_gen_chunk_size.params.expected, crate::directives::gen_chunk_size(
-                    &fixture_gen_chunk_size.params.chunk_size,
-                    &fixture_gen_chunk_size.params.num_chunks,
-                    &fixture_gen_chunk_size.params.source_size,
-                ));
+                assert_eq!(*fixture_gen_chunk_size.params.expected, result.unwrap());
             }
         }
     }
+
+    /// Scratch directory for a single test under the system temp dir, named
+    /// after the test so parallel test runs don't collide.
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("tarsplit_test_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Writes a tar archive at `path` containing `entries` (path, content).
+    fn write_source_archive(path: &std::path::Path, entries: &[(&str, &[u8])]) {
+        let mut builder = tar::Builder::new(std::fs::File::create(path).unwrap());
+        for (entry_path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, entry_path, *content).unwrap();
+        }
+        builder.finish().unwrap();
+    }
+
+    /// Reads every entry out of a tar archive into a (path, content) vec
+    /// sorted by path, so round-trip assertions don't depend on entry order.
+    fn read_archive_entries(path: &std::path::Path) -> Vec<(String, Vec<u8>)> {
+        let mut archive = tar::Archive::new(std::fs::File::open(path).unwrap());
+        let mut entries: Vec<(String, Vec<u8>)> = archive.entries().unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let path = entry.path().unwrap().to_string_lossy().into_owned();
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data).unwrap();
+                (path, data)
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    fn base_split_args(source: &std::path::Path, target: &std::path::Path) -> TarsplitDirectiveArgs {
+        TarsplitDirectiveArgs {
+            chunk_size: None,
+            num_chunks: Some(2),
+            prefix: "split".to_string(),
+            compression: Compression::None,
+            max_entries: None,
+            max_total_size: None,
+            cdc: false,
+            jobs: None,
+            max_buffered_bytes: None,
+            source: source.to_string_lossy().into_owned(),
+            target: target.to_string_lossy().into_owned(),
+        }
+    }
+
+    #[test]
+    fn test_tarsplit_tarjoin_round_trip() {
+        let dir = scratch_dir("round_trip");
+        let source_path = dir.join("source.tar");
+        let entries: Vec<(&str, &[u8])> = vec![
+            ("a.txt", b"hello world".as_slice()),
+            ("b/c.txt", b"nested file contents".as_slice()),
+            ("d.bin", &[0u8; 4096]),
+        ];
+        write_source_archive(&source_path, &entries);
+
+        tarsplit(base_split_args(&source_path, &dir)).unwrap();
+
+        let joined_path = dir.join("joined.tar");
+        tarjoin(TarjoinDirectiveArgs {
+            chunk_dir: dir.to_string_lossy().into_owned(),
+            prefix: "split".to_string(),
+            output: joined_path.to_string_lossy().into_owned(),
+        }).unwrap();
+
+        let mut expected: Vec<(String, Vec<u8>)> = entries.iter()
+            .map(|(path, content)| (path.to_string(), content.to_vec()))
+            .collect();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(expected, read_archive_entries(&joined_path));
+    }
+
+    #[test]
+    fn test_tarsplit_cdc_round_trip() {
+        let dir = scratch_dir("cdc_round_trip");
+        let source_path = dir.join("source.tar");
+        // Large enough, and varied enough, to force write_oversized_entry to
+        // split it into several content-defined parts.
+        let large: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let entries: Vec<(&str, &[u8])> = vec![("big.bin", large.as_slice())];
+        write_source_archive(&source_path, &entries);
+
+        let mut args = base_split_args(&source_path, &dir);
+        args.num_chunks = None;
+        args.chunk_size = Some(4096);
+        args.cdc = true;
+        tarsplit(args).unwrap();
+
+        let joined_path = dir.join("joined.tar");
+        tarjoin(TarjoinDirectiveArgs {
+            chunk_dir: dir.to_string_lossy().into_owned(),
+            prefix: "split".to_string(),
+            output: joined_path.to_string_lossy().into_owned(),
+        }).unwrap();
+
+        assert_eq!(vec![("big.bin".to_string(), large)], read_archive_entries(&joined_path));
+    }
+
+    #[test]
+    fn test_tarsplit_verify_and_extract() {
+        let dir = scratch_dir("verify_extract");
+        let source_path = dir.join("source.tar");
+        let entries: Vec<(&str, &[u8])> = vec![
+            ("one.txt", b"one".as_slice()),
+            ("two.txt", b"two".as_slice()),
+        ];
+        write_source_archive(&source_path, &entries);
+
+        tarsplit(base_split_args(&source_path, &dir)).unwrap();
+
+        let manifest_path = dir.join("split_source.manifest.json");
+        tarsplit_verify(VerifyDirectiveArgs {
+            manifest: manifest_path.to_string_lossy().into_owned(),
+            dir: dir.to_string_lossy().into_owned(),
+        }).unwrap();
+
+        let extract_dir = dir.join("extracted");
+        std::fs::create_dir_all(&extract_dir).unwrap();
+        tarsplit_extract(ExtractDirectiveArgs {
+            manifest: manifest_path.to_string_lossy().into_owned(),
+            dir: dir.to_string_lossy().into_owned(),
+            only: "one.txt".to_string(),
+            output: extract_dir.to_string_lossy().into_owned(),
+        }).unwrap();
+
+        assert_eq!(b"one".to_vec(), std::fs::read(extract_dir.join("one.txt")).unwrap());
+        assert!(!extract_dir.join("two.txt").exists());
+    }
 }